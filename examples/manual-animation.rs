@@ -1,11 +1,6 @@
-#![feature(random)]
+use std::time::Duration;
 
-use std::{
-    random::{DefaultRandomSource, Random},
-    time::Duration,
-};
-
-use aseprite_bevy::{AsepriteAnimation, AsepritePlugin};
+use aseprite_bevy::{AsepriteAnimation, AsepritePlugin, AsepriteState, LoopMode};
 use bevy::prelude::*;
 
 fn main() {
@@ -30,20 +25,63 @@ struct FakeDynastes(Handle<AsepriteAnimation>);
 #[derive(Component)]
 struct AnimationState(String);
 
+/// Which way a `LoopMode::PingPong` state is currently traversing its
+/// frames; unused by the other loop modes.
+#[derive(Component, Default)]
+struct PingPongDirection {
+    reverse: bool,
+}
+
 #[derive(Component, Deref, DerefMut)]
 struct AnimationTimer(Timer);
 
+/// Advances `current` by one frame according to `state.loop_mode`, flipping
+/// `reverse` as needed for `LoopMode::PingPong`. Returns `None` when
+/// `LoopMode::Once` has reached the last frame, meaning playback should
+/// freeze there.
+fn step_index(state: &AsepriteState, current: usize, reverse: &mut bool) -> Option<usize> {
+    match state.loop_mode {
+        LoopMode::Once => (current < state.last).then_some(current + 1),
+        LoopMode::Loop => Some(if current < state.last {
+            current + 1
+        } else {
+            state.first
+        }),
+        LoopMode::PingPong => {
+            if state.first == state.last {
+                return Some(state.first);
+            }
+
+            if *reverse {
+                if current > state.first {
+                    Some(current - 1)
+                } else {
+                    *reverse = false;
+                    Some(state.first + 1)
+                }
+            } else if current < state.last {
+                Some(current + 1)
+            } else {
+                *reverse = true;
+                Some(state.last - 1)
+            }
+        }
+    }
+}
+
 fn animate_sprite(
     time: Res<Time>,
     mut query: Query<(
         &FakeDynastes,
-        &mut AnimationState,
+        &AnimationState,
+        &mut PingPongDirection,
         &mut AnimationTimer,
         &mut Sprite,
+        &mut Transform,
     )>,
     aseprite_assets: Res<Assets<AsepriteAnimation>>,
 ) {
-    for (dynastes, mut state_name, mut timer, mut sprite) in &mut query {
+    for (dynastes, state_name, mut ping_pong, mut timer, mut sprite, mut transform) in &mut query {
         let Some(animation) = aseprite_assets.get(&dynastes.0) else {
             println!("This shouldn't happen?");
             continue;
@@ -56,59 +94,52 @@ fn animate_sprite(
 
         timer.tick(time.delta());
 
-        let mut should_swap_state = false;
-
-        if timer.just_finished() {
-            if let Some(atlas) = &mut sprite.texture_atlas {
-                if atlas.index == state.last {
-                    should_swap_state = true;
-                } else {
-                    atlas.index = atlas.index + 1;
-
-                    let Some(duration) = state.durations.get(atlas.index - state.first) else {
-                        println!("No frames in state");
-                        continue;
-                    };
-
-                    if timer.times_finished_this_tick() > 1 {
-                        println!(
-                            "lag experienced. {} frames missed",
-                            timer.times_finished_this_tick()
-                        );
-                    }
-
-                    timer.set_duration(Duration::from_millis(*duration as u64));
-                };
-            }
+        if !timer.just_finished() {
+            continue;
         }
 
-        if should_swap_state {
-            // Very inefficiently select a new random state
-            let ordered = animation.states.iter().collect::<Vec<_>>();
-            let Some((new_state_name, new_state)) =
-                ordered.get(usize::random(&mut DefaultRandomSource) % ordered.len())
-            else {
-                println!("No states!");
-                continue;
-            };
-
-            println!("new state: {new_state_name}");
-
-            let Some(first_duration) = new_state.durations.get(0) else {
-                println!("No frames in state");
-                continue;
-            };
-
-            sprite.texture_atlas = Some(new_state.atlas.clone());
-            state_name.0 = new_state_name.to_string();
-            timer.0 = Timer::new(
-                Duration::from_millis(*first_duration as u64),
-                TimerMode::Repeating,
-            )
+        if timer.times_finished_this_tick() > 1 {
+            println!(
+                "lag experienced. {} frames missed",
+                timer.times_finished_this_tick()
+            );
         }
+
+        let Some(atlas) = &mut sprite.texture_atlas else {
+            continue;
+        };
+
+        let Some(next_index) = step_index(state, atlas.index, &mut ping_pong.reverse) else {
+            // LoopMode::Once reached the last frame; freeze here.
+            continue;
+        };
+        atlas.index = next_index;
+
+        let Some(duration) = state.durations.get(atlas.index - state.first) else {
+            println!("No frames in state");
+            continue;
+        };
+
+        timer.set_duration(Duration::from_millis(*duration));
+        apply_frame_transform(&mut transform, state, atlas.index);
     }
 }
 
+/// Shifts and rotates `transform` so a trimmed/rotated frame stays visually
+/// centered, using the per-frame data `AsepriteState` carries alongside
+/// `durations`.
+fn apply_frame_transform(transform: &mut Transform, state: &AsepriteState, index: usize) {
+    let offset = state.offset(index).unwrap_or_default();
+    transform.translation.x = offset.x * transform.scale.x;
+    transform.translation.y = -offset.y * transform.scale.y;
+
+    transform.rotation = if state.is_rotated(index).unwrap_or(false) {
+        Quat::from_rotation_z(-std::f32::consts::FRAC_PI_2)
+    } else {
+        Quat::IDENTITY
+    };
+}
+
 fn render_on_load(
     mut commands: Commands,
     mut unloaded: Query<(Entity, &FakeDynastes), Without<Sprite>>,
@@ -119,8 +150,14 @@ fn render_on_load(
             continue;
         };
 
-        // Get an arbitrary first state
-        let Some((state_name, state)) = animation.states.iter().next() else {
+        // Start on the sheet's configured `default_state`, falling back to
+        // an arbitrary state if none was set or it doesn't exist.
+        let default_entry = animation
+            .default_state
+            .as_deref()
+            .and_then(|name| animation.states.get_key_value(name));
+        let Some((state_name, state)) = default_entry.or_else(|| animation.states.iter().next())
+        else {
             println!("No states!");
             continue;
         };
@@ -130,10 +167,14 @@ fn render_on_load(
             continue;
         };
 
+        let mut transform = Transform::from_scale(Vec3::splat(2.0));
+        apply_frame_transform(&mut transform, state, state.first);
+
         commands.entity(entity).insert((
             Sprite::from_atlas_image(animation.image.clone(), state.atlas.clone()),
-            Transform::from_scale(Vec3::splat(2.0)),
+            transform,
             AnimationState(state_name.clone()),
+            PingPongDirection::default(),
             AnimationTimer(Timer::new(
                 Duration::from_millis(*first_duration as u64),
                 TimerMode::Repeating,
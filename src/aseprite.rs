@@ -5,14 +5,14 @@ use bevy::{
     asset::{io::Reader, Asset, AssetLoader, Handle, LoadContext},
     color::{Color, Srgba},
     image::Image,
-    math::{URect, UVec2},
+    math::{URect, UVec2, Vec2},
     reflect::TypePath,
     sprite::{TextureAtlas, TextureAtlasLayout},
 };
 use dynastes::{State, StateSystem};
 use serde::{
     de::{self, Visitor},
-    Deserialize, Deserializer,
+    Deserialize, Deserializer, Serialize,
 };
 use thiserror::Error;
 
@@ -21,6 +21,10 @@ use thiserror::Error;
 pub enum AnimationDirection {
     Forward,
     Reverse,
+    #[serde(rename = "pingpong")]
+    PingPong,
+    #[serde(rename = "pingpong_reverse")]
+    PingPongReverse,
 }
 
 #[derive(Debug, Clone, Copy, Deserialize, PartialEq, Eq)]
@@ -45,66 +49,111 @@ pub struct AsepriteState {
     pub atlas: TextureAtlas,
     /// Duration of a frame (ms)
     pub durations: Vec<u64>,
+    /// Pixel offset of the trimmed frame from the center of its untrimmed
+    /// canvas, for re-centering a trimmed frame at draw time.
+    pub offsets: Vec<Vec2>,
+    /// Whether the frame is stored rotated 90° in the atlas.
+    pub rotations: Vec<bool>,
+    /// Looping behavior to apply once the last frame is reached, resolved
+    /// from [`AsepriteLoaderSettings`] at load time.
+    pub loop_mode: LoopMode,
     pub first: usize,
     pub last: usize,
 }
 
-impl AsepriteState {
-    fn new(
-        tag: &FrameTag,
-        aseprite_json: &AsepriteJson,
-        load_context: &mut LoadContext<'_>,
-    ) -> Result<Self, AsepriteError> {
-        match aseprite_json.frames {
-            AsepriteFrames::Dict(_) => {
-                return Err(AsepriteError::Unsupported(
-                    "Frames as dictionary".to_string(),
-                ))
-            },
-            _ => {},
+/// `AsepriteState` minus the parts that depend on the final, shared
+/// `TextureAtlasLayout` handle, which isn't known until every tag has
+/// contributed its frames to that layout.
+pub(crate) struct PendingState {
+    name: String,
+    direction: AnimationDirection,
+    color: Color,
+    durations: Vec<u64>,
+    offsets: Vec<Vec2>,
+    rotations: Vec<bool>,
+    loop_mode: LoopMode,
+    first: usize,
+    last: usize,
+}
+
+impl PendingState {
+    pub(crate) fn name(&self) -> &str {
+        &self.name
+    }
+
+    pub(crate) fn into_state(self, layout: Handle<TextureAtlasLayout>) -> AsepriteState {
+        let atlas = TextureAtlas {
+            layout,
+            index: self.first,
+        };
+
+        AsepriteState {
+            name: self.name,
+            direction: self.direction,
+            color: self.color,
+            atlas,
+            durations: self.durations,
+            offsets: self.offsets,
+            rotations: self.rotations,
+            loop_mode: self.loop_mode,
+            first: self.first,
+            last: self.last,
         }
+    }
+}
 
+impl AsepriteState {
+    /// Appends `tag`'s frames (in play order) to the shared `atlas_layout`
+    /// starting at `first`, an absolute index into that layout rather than a
+    /// tag-local one, so every tag in an image can share one layout asset.
+    /// Each frame's duration is divided by `speed_multiplier`, so values
+    /// above `1.0` play faster and values below `1.0` play slower.
+    pub(crate) fn build(
+        tag: &FrameTag,
+        frames: &AsepriteFrames,
+        first: usize,
+        loop_mode: LoopMode,
+        speed_multiplier: f32,
+        atlas_layout: &mut TextureAtlasLayout,
+    ) -> Result<PendingState, AsepriteError> {
         if tag.from > tag.to {
             return Err(AsepriteError::InvalidTagRange(tag.from, tag.to));
         }
 
-        let mut durations = Vec::with_capacity(tag.to - tag.from + 1);
-        // Potential optimization is one layout for all states in an image
-        let mut atlas_layout = TextureAtlasLayout::new_empty(aseprite_json.meta.size.into());
-        for frame in aseprite_json.frames.slice(tag.from, tag.to, tag.direction) {
-            if frame.rotated {
-                return Err(AsepriteError::Unsupported("Frame Rotation".to_string()));
-            }
-            if frame.trimmed || UVec2::from(frame.source_size) != frame.frame.size() {
-                return Err(AsepriteError::Unsupported("Sprite Trimming".to_string()));
-            }
-            if frame.frame.size() != frame.sprite_source_size.size() {
-                return Err(AsepriteError::Unsupported("Cel Trimming".to_string()));
-            }
-
+        let mut durations = Vec::new();
+        let mut offsets = Vec::new();
+        let mut rotations = Vec::new();
+        for frame in frames.slice(tag.from, tag.to, tag.direction) {
             atlas_layout.add_texture(frame.frame.into());
-            durations.push(frame.duration);
+            durations.push((frame.duration as f32 / speed_multiplier) as u64);
+            offsets.push(frame.offset());
+            rotations.push(frame.rotated);
         }
-        let layout_handle = load_context.add_labeled_asset(tag.name.clone(), atlas_layout);
+        let last = first + durations.len() - 1;
 
-        let first = 0;
-        let last = durations.len() - 1;
-
-        let atlas = TextureAtlas {
-            layout: layout_handle,
-            index: first,
-        };
-
-        Ok(Self {
+        Ok(PendingState {
             name: tag.name.clone(),
             direction: tag.direction,
             color: tag.color.into(),
-            atlas,
             durations,
+            offsets,
+            rotations,
+            loop_mode,
             first,
             last,
         })
     }
+
+    /// Pixel offset to re-center the frame at `index` within its untrimmed
+    /// canvas; apply this as a shift to the sprite's `Transform`.
+    pub fn offset(&self, index: usize) -> Option<Vec2> {
+        self.offsets.get(index - self.first).copied()
+    }
+
+    /// Whether the frame at `index` is stored rotated 90° in the atlas.
+    pub fn is_rotated(&self, index: usize) -> Option<bool> {
+        self.rotations.get(index - self.first).copied()
+    }
 }
 
 impl State for AsepriteState {
@@ -129,24 +178,100 @@ impl State for AsepriteState {
 pub struct AsepriteAnimation {
     pub image: Handle<Image>,
     pub states: HashMap<String, AsepriteState>,
+    /// Named slices (pivots, 9-patch regions, hitboxes) defined on the sheet.
+    pub slices: HashMap<String, AsepriteSlice>,
+    /// Tag to start playback on, from [`AsepriteLoaderSettings::default_state`].
+    pub default_state: Option<String>,
+}
+
+/// Builds one shared `TextureAtlasLayout` covering every tag's frames, and
+/// the per-tag state data referencing absolute offsets into it. Shared by
+/// both the JSON+PNG loader and the native binary loader so they produce an
+/// identical asset shape.
+///
+/// Known limitation: this only shares the `TextureAtlasLayout` *asset*
+/// across tags, not the rects inside it. Playback steps `atlas.index`
+/// contiguously through `first..=last`, and a ping-pong tag already relies
+/// on revisiting the same source frame within its own range (see
+/// `play_order`), so each tag's play-order sequence is appended to the
+/// layout as-is. If two tags reference the same underlying frame (e.g. a
+/// shared idle pose), that frame's rect is still stored once per tag,
+/// exactly as it was with one `TextureAtlasLayout` per tag. Deduplicating
+/// across tags would need index lookups indirected through a rect cache
+/// instead of the current 1:1 `atlas.index` -> layout-slot stepping.
+pub(crate) fn build_states(
+    frames: &AsepriteFrames,
+    frame_tags: &[FrameTag],
+    settings: &AsepriteLoaderSettings,
+    canvas_size: UVec2,
+) -> Result<(TextureAtlasLayout, Vec<PendingState>), AsepriteError> {
+    let mut atlas_layout = TextureAtlasLayout::new_empty(canvas_size);
+    let mut pending = Vec::new();
+    let mut next_first = 0;
+    for tag in frame_tags {
+        let loop_mode = settings
+            .tag_loop_modes
+            .get(&tag.name)
+            .copied()
+            .unwrap_or(settings.loop_mode);
+        let state = AsepriteState::build(
+            tag,
+            frames,
+            next_first,
+            loop_mode,
+            settings.speed_multiplier,
+            &mut atlas_layout,
+        )?;
+        next_first = state.last + 1;
+        pending.push(state);
+    }
+    Ok((atlas_layout, pending))
 }
 
 impl AsepriteAnimation {
     fn new(
         aseprite_json: AsepriteJson,
+        settings: &AsepriteLoaderSettings,
         load_context: &mut LoadContext<'_>,
     ) -> Result<Self, AsepriteError> {
-        let mut states = HashMap::new();
-        for tag in &aseprite_json.meta.frame_tags {
-            states.insert(
-                tag.name.clone(),
-                AsepriteState::new(tag, &aseprite_json, load_context)?,
-            );
+        if matches!(aseprite_json.frames, AsepriteFrames::Dict(_)) {
+            return Err(AsepriteError::Unsupported(
+                "Frames as dictionary".to_string(),
+            ));
         }
 
+        let (atlas_layout, pending) = build_states(
+            &aseprite_json.frames,
+            &aseprite_json.meta.frame_tags,
+            settings,
+            aseprite_json.meta.size.into(),
+        )?;
+
+        let layout_handle =
+            load_context.add_labeled_asset("atlas_layout".to_string(), atlas_layout);
+
+        let states = pending
+            .into_iter()
+            .map(|state| (state.name.clone(), state.into_state(layout_handle.clone())))
+            .collect();
+
+        let slices = aseprite_json
+            .meta
+            .slices
+            .unwrap_or_default()
+            .into_iter()
+            .map(AsepriteSlice::from)
+            .map(|slice| (slice.name.clone(), slice))
+            .collect();
+
         let image = load_context.load::<Image>(aseprite_json.meta.image);
 
-        Ok(Self { image, states })
+        Ok(Self {
+            image,
+            states,
+            slices,
+            default_state: settings.default_state.clone(),
+        })
     }
 }
 
@@ -162,6 +287,46 @@ impl StateSystem for AsepriteAnimation {
     }
 }
 
+/// How a state behaves once it reaches its last frame.
+#[derive(Debug, Clone, Copy, Deserialize, Serialize, PartialEq, Eq, Default)]
+#[serde(rename_all = "lowercase")]
+pub enum LoopMode {
+    #[default]
+    Loop,
+    Once,
+    PingPong,
+}
+
+/// Loader settings for [`AsepriteLoader`], settable via a `.meta` file so
+/// default tag, looping, and playback speed can be configured declaratively
+/// instead of in gameplay systems.
+#[derive(Debug, Clone, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AsepriteLoaderSettings {
+    /// Tag to record as [`AsepriteAnimation::default_state`]. Not validated
+    /// against the sheet's tags at load time.
+    pub default_state: Option<String>,
+    /// Looping behavior applied to every tag, unless overridden per-tag in
+    /// `tag_loop_modes`.
+    pub loop_mode: LoopMode,
+    /// Per-tag overrides of `loop_mode`, keyed by tag name.
+    pub tag_loop_modes: HashMap<String, LoopMode>,
+    /// Divides every frame's duration at load time, so `2.0` plays the
+    /// animation twice as fast and `0.5` plays it at half speed.
+    pub speed_multiplier: f32,
+}
+
+impl Default for AsepriteLoaderSettings {
+    fn default() -> Self {
+        Self {
+            default_state: None,
+            loop_mode: LoopMode::default(),
+            tag_loop_modes: HashMap::new(),
+            speed_multiplier: 1.0,
+        }
+    }
+}
+
 #[derive(Default)]
 pub struct AsepriteLoader;
 
@@ -189,12 +354,12 @@ pub enum AsepriteError {
 
 impl AssetLoader for AsepriteLoader {
     type Asset = AsepriteAnimation;
-    type Settings = ();
+    type Settings = AsepriteLoaderSettings;
     type Error = AsepriteLoaderError;
     async fn load(
         &self,
         reader: &mut dyn Reader,
-        _settings: &(),
+        settings: &AsepriteLoaderSettings,
         load_context: &mut LoadContext<'_>,
     ) -> Result<Self::Asset, Self::Error> {
         let mut bytes = Vec::new();
@@ -202,7 +367,7 @@ impl AssetLoader for AsepriteLoader {
 
         let aseprite: AsepriteJson = serde_json::from_slice(&bytes)?;
 
-        Ok(AsepriteAnimation::new(aseprite, load_context)?)
+        Ok(AsepriteAnimation::new(aseprite, settings, load_context)?)
     }
 
     fn extensions(&self) -> &[&str] {
@@ -211,7 +376,7 @@ impl AssetLoader for AsepriteLoader {
 }
 
 #[derive(Debug, Clone, Copy, Deserialize, PartialEq)]
-struct AsepriteRect {
+pub(crate) struct AsepriteRect {
     x: u32,
     y: u32,
     w: u32,
@@ -219,11 +384,8 @@ struct AsepriteRect {
 }
 
 impl AsepriteRect {
-    fn size(&self) -> UVec2 {
-        UVec2 {
-            x: self.w,
-            y: self.h,
-        }
+    pub(crate) fn new(x: u32, y: u32, w: u32, h: u32) -> Self {
+        Self { x, y, w, h }
     }
 }
 
@@ -240,11 +402,17 @@ impl From<AsepriteRect> for URect {
 }
 
 #[derive(Debug, Clone, Copy, Deserialize, PartialEq)]
-struct AsepriteSize {
+pub(crate) struct AsepriteSize {
     w: u32,
     h: u32,
 }
 
+impl AsepriteSize {
+    pub(crate) fn new(w: u32, h: u32) -> Self {
+        Self { w, h }
+    }
+}
+
 impl From<AsepriteSize> for UVec2 {
     fn from(v: AsepriteSize) -> Self {
         Self { x: v.w, y: v.h }
@@ -258,16 +426,45 @@ pub struct AsepriteFrame {
     _filename: String,
     frame: AsepriteRect,
     rotated: bool,
-    trimmed: bool,
+    #[serde(rename = "trimmed")]
+    _trimmed: bool,
     sprite_source_size: AsepriteRect,
     source_size: AsepriteSize,
     /// Duration the frame is shown (ms)
     duration: u64,
 }
 
+impl AsepriteFrame {
+    /// Builds a frame that isn't trimmed or rotated, i.e. `frame` already
+    /// covers the whole `source_size` canvas. Used by loaders, like the
+    /// native binary one, that don't go through Aseprite's JSON export.
+    pub(crate) fn untrimmed(frame: AsepriteRect, duration: u64) -> Self {
+        Self {
+            _filename: String::new(),
+            sprite_source_size: frame,
+            source_size: AsepriteSize::new(frame.w, frame.h),
+            frame,
+            rotated: false,
+            _trimmed: false,
+            duration,
+        }
+    }
+
+    /// Pixel offset of this frame's trimmed content from the center of its
+    /// untrimmed `source_size` canvas.
+    fn offset(&self) -> Vec2 {
+        let canvas_center = Vec2::new(self.source_size.w as f32, self.source_size.h as f32) / 2.0;
+        let sprite_center = Vec2::new(
+            self.sprite_source_size.x as f32 + self.sprite_source_size.w as f32 / 2.0,
+            self.sprite_source_size.y as f32 + self.sprite_source_size.h as f32 / 2.0,
+        );
+        sprite_center - canvas_center
+    }
+}
+
 #[derive(Debug, Clone, Deserialize)]
 #[serde(untagged)]
-enum AsepriteFrames {
+pub(crate) enum AsepriteFrames {
     List(Vec<AsepriteFrame>),
     Dict(HashMap<String, AsepriteFrame>),
 }
@@ -282,7 +479,7 @@ impl AsepriteFrames {
                 // It would be too much work to parse, especially since the user could choose
                 // to omit the frame number / start from different numbers etc.
                 todo!()
-            },
+            }
         }
     }
 }
@@ -297,66 +494,63 @@ impl AsepriteFrames {
     where
         'a: 'b,
     {
-        let next_i = match direction {
-            AnimationDirection::Forward => from,
-            AnimationDirection::Reverse => to,
-        };
-
         FramesIter {
             frames: self,
-            direction,
-            from,
-            to,
-            next_i,
+            order: play_order(from, to, direction),
+            pos: 0,
         }
     }
 }
 
-struct FramesIter<'a> {
-    frames: &'a AsepriteFrames,
-    direction: AnimationDirection,
-    from: usize,
-    to: usize,
-    next_i: usize,
-}
-
-impl<'a> FramesIter<'a> {
-    fn next_index(&mut self) -> Option<usize> {
-        match self.direction {
-            AnimationDirection::Forward => {
-                if self.next_i <= self.to {
-                    let old = self.next_i;
-                    self.next_i += 1;
-                    Some(old)
-                } else {
-                    None
-                }
-            },
-            AnimationDirection::Reverse => {
-                if self.next_i >= self.from {
-                    let old = self.next_i;
-                    self.next_i -= 1;
-                    Some(old)
-                } else {
-                    None
-                }
-            },
+/// Expands a tag's `from..=to` range into the sequence of frame indices it is
+/// actually played in, so a plain incrementing `atlas.index` reproduces
+/// ping-pong bouncing without any special-casing at animation time.
+fn play_order(from: usize, to: usize, direction: AnimationDirection) -> Vec<usize> {
+    match direction {
+        AnimationDirection::Forward => (from..=to).collect(),
+        AnimationDirection::Reverse => (from..=to).rev().collect(),
+        AnimationDirection::PingPong => {
+            if from == to {
+                vec![from]
+            } else {
+                (from..=to).chain((from + 1..to).rev()).collect()
+            }
+        }
+        AnimationDirection::PingPongReverse => {
+            if from == to {
+                vec![from]
+            } else {
+                (from..=to).rev().chain(from + 1..to).collect()
+            }
         }
     }
 }
 
+struct FramesIter<'a> {
+    frames: &'a AsepriteFrames,
+    order: Vec<usize>,
+    pos: usize,
+}
+
 impl<'a> Iterator for FramesIter<'a> {
     type Item = &'a AsepriteFrame;
 
     fn next(&mut self) -> Option<Self::Item> {
-        self.next_index().and_then(|i| self.frames.get(i))
+        let i = *self.order.get(self.pos)?;
+        self.pos += 1;
+        let frame = self.frames.get(i);
+        if frame.is_none() {
+            // Stay exhausted, as `FusedIterator` promises.
+            self.pos = self.order.len();
+        }
+        frame
     }
 }
 
 impl<'a> FusedIterator for FramesIter<'a> {}
 
 #[derive(Debug, Clone, Deserialize)]
-struct FrameTag {
+pub(crate) struct FrameTag {
     name: String,
     from: usize,
     to: usize,
@@ -364,6 +558,24 @@ struct FrameTag {
     color: AsepriteColor, // todo!() parse sRGB from '#<RR><GG><BB><AA>'
 }
 
+impl FrameTag {
+    pub(crate) fn new(
+        name: String,
+        from: usize,
+        to: usize,
+        direction: AnimationDirection,
+        color: AsepriteColor,
+    ) -> Self {
+        Self {
+            name,
+            from,
+            to,
+            direction,
+            color,
+        }
+    }
+}
+
 #[derive(Debug, Clone, Deserialize)]
 #[serde(rename_all = "camelCase")]
 struct AsepriteMeta {
@@ -376,8 +588,106 @@ struct AsepriteMeta {
     // aseprite allows omitting tags in addition to layers and slices but we'd have nothing to do w/o tags
     frame_tags: Vec<FrameTag>,
     layers: Option<Vec<AsepriteLayer>>,
-    // unknown what this is as my example is empty
-    slices: Option<Vec<()>>,
+    slices: Option<Vec<AsepriteSliceJson>>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct AsepriteSliceJson {
+    name: String,
+    color: AsepriteColor,
+    keys: Vec<AsepriteSliceKeyJson>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct AsepriteSliceKeyJson {
+    frame: usize,
+    bounds: AsepriteRect,
+    /// Present for 9-patch slices.
+    center: Option<AsepriteRect>,
+    pivot: Option<AsepriteSlicePivot>,
+}
+
+#[derive(Debug, Clone, Copy, Deserialize)]
+struct AsepriteSlicePivot {
+    x: u32,
+    y: u32,
+}
+
+impl From<AsepriteSlicePivot> for UVec2 {
+    fn from(v: AsepriteSlicePivot) -> Self {
+        Self { x: v.x, y: v.y }
+    }
+}
+
+/// A named Aseprite slice (pivot point, 9-patch region, or hitbox) resolved
+/// against the frames it was defined on.
+#[derive(Debug, Clone)]
+pub struct AsepriteSlice {
+    pub name: String,
+    pub color: Color,
+    /// Keys in ascending `frame` order; a key applies from its `frame` up to
+    /// (but not including) the next key's `frame`.
+    pub keys: Vec<AsepriteSliceKey>,
+}
+
+#[derive(Debug, Clone, Copy)]
+pub struct AsepriteSliceKey {
+    pub frame: usize,
+    pub bounds: URect,
+    /// The 9-patch center region, if this slice has one. Sheet-absolute,
+    /// like `bounds` (Aseprite exports it relative to `bounds`'s origin).
+    pub center: Option<URect>,
+    /// Sheet-absolute, like `bounds` (Aseprite exports it relative to
+    /// `bounds`'s origin).
+    pub pivot: Option<UVec2>,
+}
+
+impl AsepriteSlice {
+    /// The key in effect at `frame`: the last key whose `frame` is `<=` the
+    /// given frame, or `None` if `frame` precedes every key.
+    pub fn key_at(&self, frame: usize) -> Option<&AsepriteSliceKey> {
+        self.keys.iter().rev().find(|key| key.frame <= frame)
+    }
+}
+
+impl From<AsepriteSliceJson> for AsepriteSlice {
+    fn from(json: AsepriteSliceJson) -> Self {
+        let mut keys: Vec<AsepriteSliceKey> = json
+            .keys
+            .into_iter()
+            .map(|key| {
+                let origin = UVec2::new(key.bounds.x, key.bounds.y);
+                let bounds: URect = key.bounds.into();
+                // Aseprite exports `center` and `pivot` relative to this
+                // key's `bounds` origin, not the sheet, so re-add it here to
+                // give callers sheet-absolute coordinates.
+                let center = key.center.map(|center| {
+                    let center: URect = center.into();
+                    URect {
+                        min: center.min + origin,
+                        max: center.max + origin,
+                    }
+                });
+                let pivot = key.pivot.map(|pivot| UVec2::from(pivot) + origin);
+
+                AsepriteSliceKey {
+                    frame: key.frame,
+                    bounds,
+                    center,
+                    pivot,
+                }
+            })
+            .collect();
+        keys.sort_by_key(|key| key.frame);
+
+        Self {
+            name: json.name,
+            color: json.color.into(),
+            keys,
+        }
+    }
 }
 
 #[derive(Debug, Clone, Deserialize)]
@@ -389,13 +699,24 @@ struct AsepriteJson {
 
 #[derive(Debug, Clone, Copy, Deserialize)]
 #[serde(try_from = "String")]
-struct AsepriteColor {
+pub(crate) struct AsepriteColor {
     red: u8,
     green: u8,
     blue: u8,
     alpha: u8,
 }
 
+impl AsepriteColor {
+    pub(crate) fn new(red: u8, green: u8, blue: u8, alpha: u8) -> Self {
+        Self {
+            red,
+            green,
+            blue,
+            alpha,
+        }
+    }
+}
+
 #[non_exhaustive]
 #[derive(Debug, Clone, Error)]
 pub enum ColorParseError {
@@ -444,3 +765,50 @@ impl From<AsepriteColor> for Color {
         })
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn play_order_forward() {
+        assert_eq!(
+            play_order(2, 5, AnimationDirection::Forward),
+            vec![2, 3, 4, 5]
+        );
+    }
+
+    #[test]
+    fn play_order_reverse() {
+        assert_eq!(
+            play_order(2, 5, AnimationDirection::Reverse),
+            vec![5, 4, 3, 2]
+        );
+    }
+
+    #[test]
+    fn play_order_ping_pong() {
+        let order = play_order(2, 5, AnimationDirection::PingPong);
+        assert_eq!(order, vec![2, 3, 4, 5, 4, 3]);
+        assert_eq!(order.len(), 2 * (5 - 2));
+    }
+
+    #[test]
+    fn play_order_ping_pong_reverse() {
+        let order = play_order(2, 5, AnimationDirection::PingPongReverse);
+        assert_eq!(order, vec![5, 4, 3, 2, 3, 4]);
+        assert_eq!(order.len(), 2 * (5 - 2));
+    }
+
+    #[test]
+    fn play_order_single_frame_collapses_for_every_direction() {
+        for direction in [
+            AnimationDirection::Forward,
+            AnimationDirection::Reverse,
+            AnimationDirection::PingPong,
+            AnimationDirection::PingPongReverse,
+        ] {
+            assert_eq!(play_order(3, 3, direction), vec![3]);
+        }
+    }
+}
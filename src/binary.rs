@@ -0,0 +1,843 @@
+//! A second [`AssetLoader`] that reads Aseprite's native `.aseprite`/`.ase`
+//! binary format directly, so users don't need to run the Aseprite CLI to
+//! export a JSON sheet + PNG before this crate can use it. It builds the same
+//! [`AsepriteAnimation`] asset the JSON loader does, by compositing each
+//! frame's layers into one RGBA image, laying those frames out into a single
+//! combined atlas, and mapping tags the same way `build_states` does for the
+//! JSON path.
+
+use bevy::{
+    asset::{io::Reader, AssetLoader, LoadContext},
+    image::Image,
+    math::UVec2,
+    render::{
+        render_asset::RenderAssetUsages,
+        render_resource::{Extent3d, TextureDimension, TextureFormat},
+    },
+};
+use flate2::read::ZlibDecoder;
+use std::io::Read as _;
+use thiserror::Error;
+
+use crate::aseprite::{
+    build_states, AnimationDirection, AsepriteAnimation, AsepriteColor, AsepriteError,
+    AsepriteFrame, AsepriteFrames, AsepriteLoaderSettings, AsepriteRect, FrameTag,
+};
+
+const FILE_MAGIC: u16 = 0xA5E0;
+const FRAME_MAGIC: u16 = 0xF1FA;
+const CHUNK_LAYER: u16 = 0x2004;
+const CHUNK_CEL: u16 = 0x2005;
+const CHUNK_TAGS: u16 = 0x2018;
+const CHUNK_PALETTE: u16 = 0x2019;
+
+const LAYER_VISIBLE: u16 = 1;
+
+/// Reads a `.aseprite`/`.ase` file directly, skipping the JSON+PNG export
+/// step `AsepriteLoader` requires.
+#[derive(Default)]
+pub struct AsepriteBinaryLoader;
+
+#[non_exhaustive]
+#[derive(Debug, Error)]
+pub enum AsepriteBinaryLoaderError {
+    /// An [IO](std::io) Error
+    #[error("Could not load asset: {0}")]
+    Io(#[from] std::io::Error),
+    /// The `.aseprite` file's binary structure couldn't be parsed
+    #[error("Malformed .aseprite file: {0}")]
+    Malformed(String),
+    /// The file was parsed properly but still can't be used
+    #[error(transparent)]
+    Aseprite(#[from] AsepriteError),
+}
+
+impl AssetLoader for AsepriteBinaryLoader {
+    type Asset = AsepriteAnimation;
+    type Settings = AsepriteLoaderSettings;
+    type Error = AsepriteBinaryLoaderError;
+    async fn load(
+        &self,
+        reader: &mut dyn Reader,
+        settings: &AsepriteLoaderSettings,
+        load_context: &mut LoadContext<'_>,
+    ) -> Result<Self::Asset, Self::Error> {
+        let mut bytes = Vec::new();
+        reader.read_to_end(&mut bytes).await?;
+
+        let file = AseFile::parse(&bytes)?;
+
+        let canvas = UVec2::new(
+            file.width as u32 * file.frame_count() as u32,
+            file.height as u32,
+        );
+        let (atlas_layout, pending) =
+            build_states(&file.frames(), &file.frame_tags(), settings, canvas)?;
+
+        let layout_handle =
+            load_context.add_labeled_asset("atlas_layout".to_string(), atlas_layout);
+        let states = pending
+            .into_iter()
+            .map(|state| {
+                (
+                    state.name().to_string(),
+                    state.into_state(layout_handle.clone()),
+                )
+            })
+            .collect();
+
+        let image = load_context.add_labeled_asset("atlas_image".to_string(), file.composite());
+
+        Ok(AsepriteAnimation {
+            image,
+            states,
+            slices: Default::default(),
+            default_state: settings.default_state.clone(),
+        })
+    }
+
+    fn extensions(&self) -> &[&str] {
+        &["aseprite", "ase"]
+    }
+}
+
+struct AseLayer {
+    visible: bool,
+    opacity: u8,
+}
+
+struct AseCel {
+    layer_index: u16,
+    x: i16,
+    y: i16,
+    opacity: u8,
+    width: u16,
+    height: u16,
+    pixels: Vec<u8>,
+}
+
+struct AseFrame {
+    duration_ms: u64,
+    cels: Vec<AseCel>,
+}
+
+struct AseTag {
+    name: String,
+    from: u16,
+    to: u16,
+    direction: AnimationDirection,
+}
+
+struct AseFile {
+    width: u16,
+    height: u16,
+    layers: Vec<AseLayer>,
+    frames: Vec<AseFrame>,
+    tags: Vec<AseTag>,
+}
+
+impl AseFile {
+    fn frame_count(&self) -> usize {
+        self.frames.len()
+    }
+
+    /// Composites every frame's cels into one RGBA image and lays the frames
+    /// out side by side, so a plain `AsepriteRect` per frame is enough to
+    /// slice them back out of the atlas.
+    fn composite(&self) -> Image {
+        let (width, height) = (self.width as u32, self.height as u32);
+        let mut data = vec![0u8; (width as usize) * (height as usize) * self.frames.len() * 4];
+
+        for (frame_index, frame) in self.frames.iter().enumerate() {
+            let frame_x_offset = frame_index * width as usize * 4;
+            for layer_index in 0..self.layers.len() as u16 {
+                let Some(cel) = frame.cels.iter().find(|cel| cel.layer_index == layer_index) else {
+                    continue;
+                };
+                let layer = &self.layers[layer_index as usize];
+                if !layer.visible {
+                    continue;
+                }
+
+                let cel_alpha = layer.opacity as f32 / 255.0 * cel.opacity as f32 / 255.0;
+                for cy in 0..cel.height as i32 {
+                    let dst_y = cel.y as i32 + cy;
+                    if dst_y < 0 || dst_y >= height as i32 {
+                        continue;
+                    }
+                    for cx in 0..cel.width as i32 {
+                        let dst_x = cel.x as i32 + cx;
+                        if dst_x < 0 || dst_x >= width as i32 {
+                            continue;
+                        }
+
+                        let src_i = ((cy as usize * cel.width as usize) + cx as usize) * 4;
+                        let Some(src) = cel.pixels.get(src_i..src_i + 4) else {
+                            continue;
+                        };
+                        let src_a = src[3] as f32 / 255.0 * cel_alpha;
+
+                        let dst_row = dst_y as usize * (width as usize * self.frames.len() * 4);
+                        let dst_i = dst_row + frame_x_offset + dst_x as usize * 4;
+                        blend_over(&mut data[dst_i..dst_i + 4], src, src_a);
+                    }
+                }
+            }
+        }
+
+        Image::new(
+            Extent3d {
+                width: width * self.frames.len() as u32,
+                height,
+                depth_or_array_layers: 1,
+            },
+            TextureDimension::D2,
+            data,
+            TextureFormat::Rgba8UnormSrgb,
+            RenderAssetUsages::default(),
+        )
+    }
+
+    /// Maps every original frame, untrimmed, to its slot in the composited
+    /// atlas built by [`AseFile::composite`].
+    fn frames(&self) -> AsepriteFrames {
+        AsepriteFrames::List(
+            self.frames
+                .iter()
+                .enumerate()
+                .map(|(i, frame)| {
+                    let rect = AsepriteRect::new(
+                        i as u32 * self.width as u32,
+                        0,
+                        self.width as u32,
+                        self.height as u32,
+                    );
+                    AsepriteFrame::untrimmed(rect, frame.duration_ms)
+                })
+                .collect(),
+        )
+    }
+
+    fn frame_tags(&self) -> Vec<FrameTag> {
+        self.tags
+            .iter()
+            .map(|tag| {
+                FrameTag::new(
+                    tag.name.clone(),
+                    tag.from as usize,
+                    tag.to as usize,
+                    tag.direction,
+                    AsepriteColor::new(255, 255, 255, 255),
+                )
+            })
+            .collect()
+    }
+
+    fn parse(bytes: &[u8]) -> Result<Self, AsepriteBinaryLoaderError> {
+        let mut cursor = ByteCursor::new(bytes);
+
+        cursor.skip(4)?; // file size
+        let magic = cursor.u16()?;
+        if magic != FILE_MAGIC {
+            return Err(AsepriteBinaryLoaderError::Malformed(format!(
+                "bad file magic {magic:#06x}"
+            )));
+        }
+        let frame_count = cursor.u16()?;
+        let width = cursor.u16()?;
+        let height = cursor.u16()?;
+        let depth = cursor.u16()?;
+        cursor.skip(4)?; // flags
+        cursor.skip(2)?; // speed (deprecated)
+        cursor.skip(8)?; // two reserved dwords
+        let transparent_index = cursor.u8()?;
+        cursor.skip(128 - 14 - 4 - 2 - 8 - 1)?; // rest of the 128-byte header
+
+        let mut layers = Vec::new();
+        let mut frames = Vec::new();
+        let mut tags = Vec::new();
+        let mut palette: Vec<[u8; 4]> = Vec::new();
+        let mut layer_cels: Vec<std::collections::HashMap<u16, AseCel>> = Vec::new();
+
+        for _ in 0..frame_count {
+            cursor.skip(4)?; // frame size
+            let frame_magic = cursor.u16()?;
+            if frame_magic != FRAME_MAGIC {
+                return Err(AsepriteBinaryLoaderError::Malformed(format!(
+                    "bad frame magic {frame_magic:#06x}"
+                )));
+            }
+            let old_chunk_count = cursor.u16()?;
+            let duration_ms = cursor.u16()?;
+            cursor.skip(2)?; // reserved
+            let new_chunk_count = cursor.u32()?;
+            let chunk_count = if old_chunk_count == 0xFFFF {
+                new_chunk_count
+            } else {
+                old_chunk_count as u32
+            };
+
+            let mut cels = Vec::new();
+            let mut this_frame_cels = std::collections::HashMap::new();
+            for _ in 0..chunk_count {
+                let chunk_start = cursor.pos();
+                let chunk_size = cursor.u32()? as usize;
+                let chunk_type = cursor.u16()?;
+                let chunk_end = chunk_start + chunk_size;
+
+                match chunk_type {
+                    CHUNK_LAYER => {
+                        let flags = cursor.u16()?;
+                        cursor.skip(2)?; // layer type
+                        cursor.skip(2)?; // child level
+                        cursor.skip(2)?; // default width
+                        cursor.skip(2)?; // default height
+                        cursor.skip(2)?; // blend mode
+                        let opacity = cursor.u8()?;
+                        layers.push(AseLayer {
+                            visible: flags & LAYER_VISIBLE != 0,
+                            opacity,
+                        });
+                    }
+                    CHUNK_CEL => {
+                        let layer_index = cursor.u16()?;
+                        let x = cursor.i16()?;
+                        let y = cursor.i16()?;
+                        let opacity = cursor.u8()?;
+                        let cel_type = cursor.u16()?;
+                        cursor.skip(7)?; // z-index + reserved
+
+                        let cel = match cel_type {
+                            0 | 2 => {
+                                let cel_width = cursor.u16()?;
+                                let cel_height = cursor.u16()?;
+                                let raw = cursor.bytes_until(chunk_end)?;
+                                let pixels = if cel_type == 2 {
+                                    decompress(raw)?
+                                } else {
+                                    raw.to_vec()
+                                };
+                                let pixels =
+                                    to_rgba8(&pixels, depth, &palette, transparent_index)?;
+                                AseCel {
+                                    layer_index,
+                                    x,
+                                    y,
+                                    opacity,
+                                    width: cel_width,
+                                    height: cel_height,
+                                    pixels,
+                                }
+                            }
+                            1 => {
+                                let linked_frame = cursor.u16()? as usize;
+                                let Some(source) = layer_cels
+                                    .get(linked_frame)
+                                    .and_then(|cels| cels.get(&layer_index))
+                                else {
+                                    return Err(AsepriteBinaryLoaderError::Malformed(
+                                        "linked cel references a missing frame/layer".to_string(),
+                                    ));
+                                };
+                                AseCel {
+                                    layer_index,
+                                    x,
+                                    y,
+                                    opacity,
+                                    width: source.width,
+                                    height: source.height,
+                                    pixels: source.pixels.clone(),
+                                }
+                            }
+                            _ => {
+                                cursor.seek(chunk_end);
+                                continue;
+                            }
+                        };
+                        this_frame_cels.insert(layer_index, clone_cel(&cel));
+                        cels.push(cel);
+                    }
+                    CHUNK_PALETTE => {
+                        let new_size = cursor.u32()? as usize;
+                        let first = cursor.u32()? as usize;
+                        let last = cursor.u32()? as usize;
+                        cursor.skip(8)?; // reserved
+                        if palette.len() < new_size {
+                            palette.resize(new_size, [0, 0, 0, 255]);
+                        }
+                        for i in first..=last {
+                            let flags = cursor.u16()?;
+                            let r = cursor.u8()?;
+                            let g = cursor.u8()?;
+                            let b = cursor.u8()?;
+                            let a = cursor.u8()?;
+                            if let Some(entry) = palette.get_mut(i) {
+                                *entry = [r, g, b, a];
+                            }
+                            if flags & 1 != 0 {
+                                cursor.string()?; // color name, unused
+                            }
+                        }
+                    }
+                    CHUNK_TAGS => {
+                        let tag_count = cursor.u16()?;
+                        cursor.skip(8)?; // reserved
+                        for _ in 0..tag_count {
+                            let from = cursor.u16()?;
+                            let to = cursor.u16()?;
+                            let loop_direction = cursor.u8()?;
+                            cursor.skip(2)?; // repeat
+                            cursor.skip(6)?; // reserved
+                            cursor.skip(3)?; // deprecated RGB color
+                            cursor.skip(1)?; // extra byte
+                            let name = cursor.string()?;
+
+                            let direction = match loop_direction {
+                                0 => AnimationDirection::Forward,
+                                1 => AnimationDirection::Reverse,
+                                2 => AnimationDirection::PingPong,
+                                3 => AnimationDirection::PingPongReverse,
+                                other => {
+                                    return Err(AsepriteBinaryLoaderError::Malformed(format!(
+                                        "unknown tag loop direction {other}"
+                                    )))
+                                }
+                            };
+
+                            tags.push(AseTag {
+                                name,
+                                from,
+                                to,
+                                direction,
+                            });
+                        }
+                    }
+                    _ => {}
+                }
+
+                cursor.seek(chunk_end);
+            }
+
+            layer_cels.push(this_frame_cels);
+            frames.push(AseFrame {
+                duration_ms: duration_ms as u64,
+                cels,
+            });
+        }
+
+        Ok(Self {
+            width,
+            height,
+            layers,
+            frames,
+            tags,
+        })
+    }
+}
+
+/// `AseCel` doesn't implement `Clone` itself (its `pixels` buffer is moved
+/// into the frame's cel list); this is only used to keep a lookup copy for
+/// later linked cels to borrow from.
+fn clone_cel(cel: &AseCel) -> AseCel {
+    AseCel {
+        layer_index: cel.layer_index,
+        x: cel.x,
+        y: cel.y,
+        opacity: cel.opacity,
+        width: cel.width,
+        height: cel.height,
+        pixels: cel.pixels.clone(),
+    }
+}
+
+/// Normal-mode "source over" compositing of one `rgba` pixel onto `dst`.
+fn blend_over(dst: &mut [u8], src: &[u8], src_a: f32) {
+    let dst_a = dst[3] as f32 / 255.0;
+    let out_a = src_a + dst_a * (1.0 - src_a);
+    if out_a <= 0.0 {
+        return;
+    }
+    for c in 0..3 {
+        let out = (src[c] as f32 * src_a + dst[c] as f32 * dst_a * (1.0 - src_a)) / out_a;
+        dst[c] = out.round().clamp(0.0, 255.0) as u8;
+    }
+    dst[3] = (out_a * 255.0).round().clamp(0.0, 255.0) as u8;
+}
+
+/// Converts raw cel pixel data (8/16/32 bpp) to RGBA8. Indexed (8bpp) pixels
+/// are palette indices resolved against the file's `CHUNK_PALETTE` data;
+/// `transparent_index` forces that one index fully transparent, as Aseprite
+/// does for indexed sprites regardless of the palette's own alpha entry.
+fn to_rgba8(
+    pixels: &[u8],
+    depth: u16,
+    palette: &[[u8; 4]],
+    transparent_index: u8,
+) -> Result<Vec<u8>, AsepriteBinaryLoaderError> {
+    match depth {
+        32 => Ok(pixels.to_vec()),
+        16 => Ok(pixels
+            .chunks_exact(2)
+            .flat_map(|px| [px[0], px[0], px[0], px[1]])
+            .collect()),
+        8 => Ok(pixels
+            .iter()
+            .flat_map(|&index| {
+                let [r, g, b, a] = palette.get(index as usize).copied().unwrap_or([0, 0, 0, 0]);
+                let a = if index == transparent_index { 0 } else { a };
+                [r, g, b, a]
+            })
+            .collect()),
+        other => Err(AsepriteBinaryLoaderError::Aseprite(
+            AsepriteError::Unsupported(format!("{other}-bit color depth")),
+        )),
+    }
+}
+
+fn decompress(bytes: &[u8]) -> Result<Vec<u8>, AsepriteBinaryLoaderError> {
+    let mut out = Vec::new();
+    ZlibDecoder::new(bytes)
+        .read_to_end(&mut out)
+        .map_err(|e| AsepriteBinaryLoaderError::Malformed(format!("zlib: {e}")))?;
+    Ok(out)
+}
+
+struct ByteCursor<'a> {
+    bytes: &'a [u8],
+    pos: usize,
+}
+
+impl<'a> ByteCursor<'a> {
+    fn new(bytes: &'a [u8]) -> Self {
+        Self { bytes, pos: 0 }
+    }
+
+    fn pos(&self) -> usize {
+        self.pos
+    }
+
+    fn seek(&mut self, pos: usize) {
+        self.pos = pos;
+    }
+
+    fn take(&mut self, n: usize) -> Result<&'a [u8], AsepriteBinaryLoaderError> {
+        let slice = self.bytes.get(self.pos..self.pos + n).ok_or_else(|| {
+            AsepriteBinaryLoaderError::Malformed("unexpected end of file".to_string())
+        })?;
+        self.pos += n;
+        Ok(slice)
+    }
+
+    fn skip(&mut self, n: usize) -> Result<(), AsepriteBinaryLoaderError> {
+        self.take(n).map(|_| ())
+    }
+
+    fn u8(&mut self) -> Result<u8, AsepriteBinaryLoaderError> {
+        Ok(self.take(1)?[0])
+    }
+
+    fn u16(&mut self) -> Result<u16, AsepriteBinaryLoaderError> {
+        Ok(u16::from_le_bytes(self.take(2)?.try_into().unwrap()))
+    }
+
+    fn i16(&mut self) -> Result<i16, AsepriteBinaryLoaderError> {
+        Ok(i16::from_le_bytes(self.take(2)?.try_into().unwrap()))
+    }
+
+    fn u32(&mut self) -> Result<u32, AsepriteBinaryLoaderError> {
+        Ok(u32::from_le_bytes(self.take(4)?.try_into().unwrap()))
+    }
+
+    fn bytes_until(&mut self, end: usize) -> Result<&'a [u8], AsepriteBinaryLoaderError> {
+        let n = end.saturating_sub(self.pos);
+        self.take(n)
+    }
+
+    /// A Pascal-style string: a `u16` byte length followed by UTF-8 bytes.
+    fn string(&mut self) -> Result<String, AsepriteBinaryLoaderError> {
+        let len = self.u16()? as usize;
+        let bytes = self.take(len)?;
+        String::from_utf8(bytes.to_vec())
+            .map_err(|e| AsepriteBinaryLoaderError::Malformed(format!("non-UTF8 string: {e}")))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn to_rgba8_32bpp_passes_pixels_through() {
+        let pixels = [10, 20, 30, 40, 50, 60, 70, 80];
+        let rgba = to_rgba8(&pixels, 32, &[], 0).unwrap();
+        assert_eq!(rgba, pixels);
+    }
+
+    #[test]
+    fn to_rgba8_16bpp_expands_grayscale_and_alpha() {
+        // one grayscale+alpha pixel: value 100, alpha 200
+        let pixels = [100, 200];
+        let rgba = to_rgba8(&pixels, 16, &[], 0).unwrap();
+        assert_eq!(rgba, vec![100, 100, 100, 200]);
+    }
+
+    #[test]
+    fn to_rgba8_8bpp_resolves_palette_indices() {
+        let palette = [[10, 20, 30, 255], [40, 50, 60, 255]];
+        let rgba = to_rgba8(&[0, 1], 8, &palette, 0xFF).unwrap();
+        assert_eq!(rgba, vec![10, 20, 30, 255, 40, 50, 60, 255]);
+    }
+
+    #[test]
+    fn to_rgba8_8bpp_transparent_index_forces_zero_alpha() {
+        let palette = [[10, 20, 30, 255]];
+        let rgba = to_rgba8(&[0], 8, &palette, 0).unwrap();
+        assert_eq!(rgba, vec![10, 20, 30, 0]);
+    }
+
+    #[test]
+    fn to_rgba8_rejects_unknown_depth() {
+        assert!(to_rgba8(&[0], 4, &[], 0).is_err());
+    }
+
+    #[test]
+    fn blend_over_opaque_source_replaces_destination() {
+        let mut dst = [0, 0, 0, 255];
+        blend_over(&mut dst, &[200, 150, 100, 255], 1.0);
+        assert_eq!(dst, [200, 150, 100, 255]);
+    }
+
+    #[test]
+    fn blend_over_half_alpha_mixes_colors() {
+        let mut dst = [0, 0, 0, 255];
+        blend_over(&mut dst, &[200, 200, 200, 255], 0.5);
+        assert_eq!(dst, [100, 100, 100, 255]);
+    }
+
+    #[test]
+    fn blend_over_transparent_source_leaves_destination() {
+        let mut dst = [10, 20, 30, 255];
+        blend_over(&mut dst, &[200, 200, 200, 0], 0.0);
+        assert_eq!(dst, [10, 20, 30, 255]);
+    }
+
+    #[test]
+    fn composite_blends_visible_layers_into_one_atlas_row() {
+        let file = AseFile {
+            width: 1,
+            height: 1,
+            layers: vec![
+                AseLayer {
+                    visible: true,
+                    opacity: 255,
+                },
+                AseLayer {
+                    visible: false,
+                    opacity: 255,
+                },
+            ],
+            frames: vec![AseFrame {
+                duration_ms: 100,
+                cels: vec![
+                    AseCel {
+                        layer_index: 0,
+                        x: 0,
+                        y: 0,
+                        opacity: 255,
+                        width: 1,
+                        height: 1,
+                        pixels: vec![10, 20, 30, 255],
+                    },
+                    // On the hidden layer; must not affect the result.
+                    AseCel {
+                        layer_index: 1,
+                        x: 0,
+                        y: 0,
+                        opacity: 255,
+                        width: 1,
+                        height: 1,
+                        pixels: vec![255, 0, 0, 255],
+                    },
+                ],
+            }],
+            tags: vec![],
+        };
+
+        let image = file.composite();
+        assert_eq!(image.data.as_deref(), Some([10, 20, 30, 255].as_slice()));
+    }
+
+    /// Appends little-endian integers and Aseprite's Pascal-style strings to
+    /// a byte buffer, mirroring `ByteCursor`'s reads so a synthetic
+    /// `.aseprite` file can be built up field by field.
+    #[derive(Default)]
+    struct Builder {
+        bytes: Vec<u8>,
+    }
+
+    impl Builder {
+        fn u8(&mut self, v: u8) -> &mut Self {
+            self.bytes.push(v);
+            self
+        }
+
+        fn u16(&mut self, v: u16) -> &mut Self {
+            self.bytes.extend_from_slice(&v.to_le_bytes());
+            self
+        }
+
+        fn i16(&mut self, v: i16) -> &mut Self {
+            self.bytes.extend_from_slice(&v.to_le_bytes());
+            self
+        }
+
+        fn u32(&mut self, v: u32) -> &mut Self {
+            self.bytes.extend_from_slice(&v.to_le_bytes());
+            self
+        }
+
+        fn skip(&mut self, n: usize) -> &mut Self {
+            self.bytes.extend(std::iter::repeat(0u8).take(n));
+            self
+        }
+
+        fn raw(&mut self, bytes: &[u8]) -> &mut Self {
+            self.bytes.extend_from_slice(bytes);
+            self
+        }
+
+        fn pascal_string(&mut self, s: &str) -> &mut Self {
+            self.u16(s.len() as u16);
+            self.raw(s.as_bytes())
+        }
+    }
+
+    /// A chunk's body, sized and type-tagged by `chunk()` once its content is
+    /// known.
+    fn chunk(chunk_type: u16, body: Builder) -> Vec<u8> {
+        let mut out = Builder::default();
+        let size = 4 + 2 + body.bytes.len() as u32;
+        out.u32(size).u16(chunk_type).raw(&body.bytes);
+        out.bytes
+    }
+
+    /// Builds a minimal one-frame `.aseprite` file with a visible layer, one
+    /// 1x1 32bpp cel, a two-entry palette, and a single forward-playing tag,
+    /// exercising every chunk type `AseFile::parse` understands.
+    fn minimal_aseprite_file() -> Vec<u8> {
+        let layer_chunk = chunk(CHUNK_LAYER, {
+            let mut b = Builder::default();
+            b.u16(LAYER_VISIBLE) // flags
+                .skip(2) // layer type
+                .skip(2) // child level
+                .skip(2) // default width
+                .skip(2) // default height
+                .skip(2) // blend mode
+                .u8(255); // opacity
+            b
+        });
+
+        let cel_chunk = chunk(CHUNK_CEL, {
+            let mut b = Builder::default();
+            b.u16(0) // layer index
+                .i16(0) // x
+                .i16(0) // y
+                .u8(255) // opacity
+                .u16(0) // cel type: raw
+                .skip(7) // z-index + reserved
+                .u16(1) // cel width
+                .u16(1) // cel height
+                .raw(&[10, 20, 30, 255]); // one RGBA pixel
+            b
+        });
+
+        let palette_chunk = chunk(CHUNK_PALETTE, {
+            let mut b = Builder::default();
+            b.u32(2) // new palette size
+                .u32(0) // first color index
+                .u32(1) // last color index
+                .skip(8); // reserved
+            for [r, g, b_, a] in [[10, 20, 30, 255], [40, 50, 60, 255]] {
+                b.u16(0).u8(r).u8(g).u8(b_).u8(a);
+            }
+            b
+        });
+
+        let tags_chunk = chunk(CHUNK_TAGS, {
+            let mut b = Builder::default();
+            b.u16(1).skip(8); // tag count + reserved
+            b.u16(0) // from
+                .u16(0) // to
+                .u8(0) // loop direction: forward
+                .skip(2) // repeat
+                .skip(6) // reserved
+                .skip(3) // deprecated RGB
+                .skip(1) // extra byte
+                .pascal_string("idle");
+            b
+        });
+
+        let mut chunks = Vec::new();
+        chunks.extend(layer_chunk);
+        chunks.extend(cel_chunk);
+        chunks.extend(palette_chunk);
+        chunks.extend(tags_chunk);
+
+        let mut frame = Builder::default();
+        frame
+            .u32(0) // frame size, unused by the parser
+            .u16(FRAME_MAGIC)
+            .u16(4) // old chunk count
+            .u16(100) // duration (ms)
+            .skip(2) // reserved
+            .u32(0) // new chunk count, unused since old_chunk_count != 0xFFFF
+            .raw(&chunks);
+
+        let mut file = Builder::default();
+        file.u32(0) // file size, unused by the parser
+            .u16(FILE_MAGIC)
+            .u16(1) // frame count
+            .u16(1) // width
+            .u16(1) // height
+            .u16(32) // color depth
+            .skip(4) // flags
+            .skip(2) // speed (deprecated)
+            .skip(8) // two reserved dwords
+            .u8(0) // transparent color index
+            .skip(128 - 14 - 4 - 2 - 8 - 1) // rest of the 128-byte header
+            .raw(&frame.bytes);
+
+        file.bytes
+    }
+
+    #[test]
+    fn parse_round_trips_header_frame_palette_and_tags() {
+        let bytes = minimal_aseprite_file();
+        let file = AseFile::parse(&bytes).unwrap();
+
+        assert_eq!(file.width, 1);
+        assert_eq!(file.height, 1);
+        assert_eq!(file.layers.len(), 1);
+        assert!(file.layers[0].visible);
+
+        assert_eq!(file.frames.len(), 1);
+        assert_eq!(file.frames[0].duration_ms, 100);
+        assert_eq!(file.frames[0].cels.len(), 1);
+        assert_eq!(file.frames[0].cels[0].pixels, vec![10, 20, 30, 255]);
+
+        assert_eq!(file.tags.len(), 1);
+        assert_eq!(file.tags[0].name, "idle");
+        assert_eq!(file.tags[0].from, 0);
+        assert_eq!(file.tags[0].to, 0);
+        assert_eq!(file.tags[0].direction, AnimationDirection::Forward);
+    }
+
+    #[test]
+    fn parse_rejects_bad_file_magic() {
+        let mut bytes = minimal_aseprite_file();
+        // Corrupt the magic number (right after the 4-byte file size field).
+        bytes[4] = 0;
+        bytes[5] = 0;
+        assert!(AseFile::parse(&bytes).is_err());
+    }
+}
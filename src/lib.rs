@@ -1,7 +1,9 @@
 mod aseprite;
+mod binary;
 
 pub use aseprite::*;
 use bevy::{app::Plugin, asset::AssetApp};
+pub use binary::{AsepriteBinaryLoader, AsepriteBinaryLoaderError};
 
 /// Re-export of dynastes
 pub use dynastes;
@@ -12,6 +14,7 @@ pub struct AsepritePlugin;
 impl Plugin for AsepritePlugin {
     fn build(&self, app: &mut bevy::prelude::App) {
         app.init_asset::<AsepriteAnimation>()
-            .init_asset_loader::<AsepriteLoader>();
+            .init_asset_loader::<AsepriteLoader>()
+            .init_asset_loader::<AsepriteBinaryLoader>();
     }
 }